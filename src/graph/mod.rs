@@ -1,10 +1,11 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::error::Error;
 use std::fmt;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 #[derive(Debug)]
-enum GraphError {
+pub enum GraphError {
     VertexNotFound(usize),
     InvalidEdge(usize, usize)
 }
@@ -24,21 +25,38 @@ impl fmt::Display for GraphError {
     }
 }
 
-struct Graph<T: Eq + PartialEq> {
-    vertices: Vec<Rc<RefCell<Vertex<T>>>>,
-    available_labels: Vec<usize>
+pub struct Graph<T: Eq + PartialEq, L: Ord + Clone> {
+    vertices: Vec<Rc<RefCell<Vertex<T, L>>>>,
+    available_labels: Vec<usize>,
+    // vertex label -> its index in `vertices`, so get_vertex/has_vertex avoid a linear scan
+    label_to_index: HashMap<usize, usize>,
+    // bumped on every add_vertex/add_edge/delete_vertex/delete_edge, so callers can cheaply detect changes
+    revision: Cell<usize>,
 }
 
-impl<T: Eq + PartialEq> Graph<T> {
+impl<T: Eq + PartialEq, L: Ord + Clone> Graph<T, L> {
 
     // returns a new empty graph
-    pub fn new() -> Graph<T>{
+    pub fn new() -> Graph<T, L> {
         Graph {
             vertices: Vec::new(),
-            available_labels: Vec::new()
+            available_labels: Vec::new(),
+            label_to_index: HashMap::new(),
+            revision: Cell::new(0),
         }
     }
 
+    // returns a counter that increases every time the graph's vertices or edges change,
+    // so callers can cheaply tell whether a cached view of the graph is stale
+    pub fn revision(&self) -> usize {
+        self.revision.get()
+    }
+
+    // bumps the revision counter
+    fn bump_revision(&self) {
+        self.revision.set(self.revision.get() + 1);
+    }
+
     // returns the next available label
     fn get_label(&mut self) -> usize {
         self.available_labels.pop().unwrap_or(self.num_vertices())
@@ -69,58 +87,63 @@ impl<T: Eq + PartialEq> Graph<T> {
             label,
             value,
             neighbors: Vec::new(),
+            by_target: BTreeMap::new(),
+            by_label: BTreeMap::new(),
+            predecessors: BTreeSet::new(),
         })));
+        self.label_to_index.insert(label, self.vertices.len() - 1);
+        self.bump_revision();
 
         label
     }
 
     // get vertex with the given label
-    pub fn get_vertex(&self, label: &usize) -> Option<&Rc<RefCell<Vertex<T>>>>{
-        for vertex in &self.vertices {
-            if vertex.borrow().label == *label {
-                return Some(vertex)
-            }
-        }
-        None
+    pub fn get_vertex(&self, label: &usize) -> Option<&Rc<RefCell<Vertex<T, L>>>>{
+        self.label_to_index.get(label).map(|&index| &self.vertices[index])
     }
 
     // returns true if the graph has a vertex with the given label
     pub fn has_vertex(&self, label: &usize) -> bool {
-        for vertex in &self.vertices {
-            if vertex.borrow().label == *label {
-                return true
-            }
-        }
-        false
+        self.label_to_index.contains_key(label)
     }
 
     // removes the vertex with the given label from the graph and returns its value
     pub fn delete_vertex(&mut self, label: &usize) -> Result<T, GraphError> {
 
-        // remove the vertex from the neighbor lists of all vertices
-        for vertex in &self.vertices {
-            vertex.borrow_mut().delete_neighbor(label);
-        }
-
-        // look for the vertex to be deleted
-        for index in 0..self.vertices.len() {
+        let index = *self.label_to_index.get(label).ok_or(GraphError::VertexNotFound(*label))?;
 
-            let vertex = self.vertices.get(index).unwrap();
-
-            if vertex.borrow().label == *label {
-
-                let value = self.vertices.remove(index);
-                self.available_labels.push(*label);
-                return Ok(Rc::try_unwrap(value).ok().unwrap().into_inner().value)
+        // only touch the vertex's actual predecessors, not every vertex in the graph
+        let predecessor_labels: Vec<usize> = self.vertices[index].borrow().predecessors.iter().cloned().collect();
+        for predecessor_label in predecessor_labels {
+            if let Some(predecessor) = self.get_vertex(&predecessor_label) {
+                predecessor.borrow_mut().delete_neighbor(label);
+            }
+        }
 
+        // this vertex is itself a predecessor of its neighbors; remove it from their predecessor sets
+        let targets: Vec<usize> = self.vertices[index].borrow().neighbors.iter().map(|(_, target)| *target).collect();
+        for target in targets {
+            if let Some(target_vertex) = self.get_vertex(&target) {
+                target_vertex.borrow_mut().predecessors.remove(label);
             }
         }
-        Err(GraphError::VertexNotFound(*label))
+
+        // swap-remove so we don't have to reindex every vertex after this one
+        let removed = self.vertices.swap_remove(index);
+        self.label_to_index.remove(label);
+        if index < self.vertices.len() {
+            let moved_label = self.vertices[index].borrow().label;
+            self.label_to_index.insert(moved_label, index);
+        }
+
+        self.available_labels.push(*label);
+        self.bump_revision();
+        Ok(Rc::try_unwrap(removed).ok().unwrap().into_inner().value)
     }
 
-    // add a directed edge to the graph from the first given label to the second given label
-    // returns true if the edge was added, false if it already exists
-    pub fn add_edge(&self, label_source: &usize, label_sink: usize) -> Result<bool, GraphError> {
+    // add a directed edge, carrying the given label, from the first given label to the second given label
+    // returns true if the edge was added, false if an edge with that label to that target already exists
+    pub fn add_edge(&self, label_source: &usize, label_sink: usize, edge_label: L) -> Result<bool, GraphError> {
 
         // return an error if the labels are equal
         if label_sink == *label_source {
@@ -133,23 +156,18 @@ impl<T: Eq + PartialEq> Graph<T> {
         }
 
         // add the sink to the source's neighbor list
-        let vertex = self.get_vertex(label_source);
-        if vertex.is_some() {
-            let mut neighbors = vertex.unwrap().borrow_mut();
-            if !neighbors.has_neighbor(&label_sink) {
-                neighbors.add_neighbor(label_sink);
+        let source_vertex = self.get_vertex(label_source).ok_or(GraphError::VertexNotFound(*label_source))?;
+        let added = source_vertex.borrow_mut().add_neighbor(label_sink, edge_label);
 
-                // edge added
-                return Ok(true)
-            }
-
-            // edge already exists
-            return Ok(false)
+        // record the source as a predecessor of the sink so delete_vertex need not scan every vertex
+        if added {
+            self.get_vertex(&label_sink).unwrap().borrow_mut().predecessors.insert(*label_source);
+            self.bump_revision();
         }
-        Err(GraphError::VertexNotFound(*label_source))
+        Ok(added)
     }
 
-    // returns true if a directed edge from the first given vertex label to the second exists
+    // returns true if a directed edge from the first given vertex label to the second exists, under any label
     pub fn has_edge(&self, label_source: &usize, label_sink: &usize) -> bool {
         let vertex = self.get_vertex(label_source);
         if vertex.is_some() {
@@ -158,8 +176,8 @@ impl<T: Eq + PartialEq> Graph<T> {
         false
     }
 
-    // remove a directed edge in the graph from the first given label to the second given label
-    // returns true if the edge was removed, false if it wasn't in the graph
+    // remove all labelled edges in the graph from the first given label to the second given label
+    // returns true if at least one edge was removed, false if there wasn't one in the graph
     pub fn delete_edge(&self, label_source: &usize, label_sink: &usize) -> Result<bool, GraphError> {
 
         // return an error if the labels are equal
@@ -172,12 +190,53 @@ impl<T: Eq + PartialEq> Graph<T> {
             return Err(GraphError::VertexNotFound(*label_sink))
         }
 
-        let vertex = self.get_vertex(label_source);
-        if vertex.is_some() {
-            let mut neighbors = vertex.unwrap().borrow_mut();
-            return Ok(neighbors.delete_neighbor(label_sink));
+        let source_vertex = self.get_vertex(label_source).ok_or(GraphError::VertexNotFound(*label_source))?;
+        let removed = source_vertex.borrow_mut().delete_neighbor(label_sink);
+
+        if removed {
+            self.get_vertex(&label_sink).unwrap().borrow_mut().predecessors.remove(label_source);
+            self.bump_revision();
         }
-        Err(GraphError::VertexNotFound(*label_source))
+        Ok(removed)
+    }
+
+    // returns the labels of vertices with a direct edge into the given vertex, backed by the reverse adjacency index
+    pub fn predecessors(&self, label: &usize) -> impl Iterator<Item=usize> {
+        let predecessors: Vec<usize> = self.get_vertex(label)
+            .map(|vertex| vertex.borrow().predecessors.iter().cloned().collect())
+            .unwrap_or_default();
+        predecessors.into_iter()
+    }
+
+    // returns the labels of edges from source to target, backed by the vertex's by_target index
+    pub fn edge_labels(&self, label_source: &usize, label_sink: &usize) -> impl Iterator<Item=L> {
+        let labels: Vec<L> = self.get_vertex(label_source)
+            .and_then(|vertex| vertex.borrow().by_target.get(label_sink).cloned())
+            .map(|set| set.into_iter().collect())
+            .unwrap_or_default();
+        labels.into_iter()
+    }
+
+    // returns the labels of children of source reachable via an edge labelled `label`, backed by the vertex's by_label index
+    pub fn find_children_with_label(&self, label_source: &usize, label: &L) -> impl Iterator<Item=usize> {
+        let targets: Vec<usize> = self.get_vertex(label_source)
+            .and_then(|vertex| vertex.borrow().by_label.get(label).cloned())
+            .map(|set| set.into_iter().collect())
+            .unwrap_or_default();
+        targets.into_iter()
+    }
+
+    // returns the labels of every vertex currently in the graph, in no particular order
+    pub fn vertex_labels(&self) -> impl Iterator<Item=usize> + '_ {
+        self.label_to_index.keys().copied()
+    }
+
+    // returns all (edge label, target) pairs leaving the given vertex, in insertion order
+    pub fn neighbors(&self, label: &usize) -> impl Iterator<Item=(L, usize)> {
+        let neighbors: Vec<(L, usize)> = self.get_vertex(label)
+            .map(|vertex| vertex.borrow().neighbors.clone())
+            .unwrap_or_default();
+        neighbors.into_iter()
     }
 
     // returns true if the graph has at least one cycle
@@ -203,7 +262,7 @@ impl<T: Eq + PartialEq> Graph<T> {
         visited.push(current_label);
         rec_stack.push(current_label);
 
-        for neighbor in &self.get_vertex(&current_label).unwrap().borrow().neighbors {
+        for (_, neighbor) in &self.get_vertex(&current_label).unwrap().borrow().neighbors {
 
             // if not visited recurse
             if !visited.contains(neighbor) {
@@ -225,43 +284,161 @@ impl<T: Eq + PartialEq> Graph<T> {
     }
 }
 
+impl<T: Eq + PartialEq + fmt::Display, L: Ord + Clone + fmt::Display> Graph<T, L> {
+
+    // serializes the graph to Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n    rankdir=LR;\n");
+
+        // emit a node per vertex, labelled with its value
+        for vertex in &self.vertices {
+            let vertex = vertex.borrow();
+            dot.push_str(&format!("    {} [label=\"{}\"];\n", vertex.label, escape_dot_label(&vertex.value.to_string())));
+        }
+
+        // emit an arc per edge, gathered by walking every vertex's neighbor list
+        for vertex in &self.vertices {
+            let vertex = vertex.borrow();
+            for (edge_label, target) in &vertex.neighbors {
+                dot.push_str(&format!("    {} -> {} [label=\"{}\"];\n", vertex.label, target, escape_dot_label(&edge_label.to_string())));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    // writes the graph's DOT representation to the given file, overwriting it if it already exists
+    pub fn print_viz(&self, filename: &str) -> std::io::Result<()> {
+        std::fs::write(filename, self.to_dot())
+    }
+}
+
+// escapes a string for embedding in a DOT label="..." attribute
+fn escape_dot_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// accumulates vertices and edges and produces a finished Graph in one pass; every queued edge is
+// validated in build before anything is materialized, and build reports the first invalid edge
+pub struct GraphBuilder<T: Eq + PartialEq, L: Ord + Clone + Default> {
+    values: Vec<T>,
+    edges: Vec<(usize, usize)>,
+    _label: std::marker::PhantomData<L>,
+}
+
+impl<T: Eq + PartialEq, L: Ord + Clone + Default> GraphBuilder<T, L> {
+
+    // returns a new empty builder
+    pub fn new() -> GraphBuilder<T, L> {
+        GraphBuilder {
+            values: Vec::new(),
+            edges: Vec::new(),
+            _label: std::marker::PhantomData,
+        }
+    }
+
+    // queues a vertex with the given value and returns the label it will have once built
+    pub fn with_vertex(&mut self, value: T) -> usize {
+        self.values.push(value);
+        self.values.len() - 1
+    }
+
+    // queues a directed edge from source to sink, to be validated and added at build time
+    pub fn with_edge(&mut self, source: usize, sink: usize) -> &mut Self {
+        self.edges.push((source, sink));
+        self
+    }
+
+    // validates that all queued edges reference existing labels and contain no self-loops,
+    // then materializes the vertices and edges into a finished Graph
+    pub fn build(self) -> Result<Graph<T, L>, GraphError> {
+        let num_vertices = self.values.len();
+        for &(source, sink) in &self.edges {
+            if source == sink {
+                return Err(GraphError::InvalidEdge(source, sink));
+            }
+            if source >= num_vertices {
+                return Err(GraphError::VertexNotFound(source));
+            }
+            if sink >= num_vertices {
+                return Err(GraphError::VertexNotFound(sink));
+            }
+        }
+
+        let mut graph = Graph::new();
+        for value in self.values {
+            graph.add_vertex(value);
+        }
+        for (source, sink) in self.edges {
+            graph.add_edge(&source, sink, L::default())?;
+        }
+        Ok(graph)
+    }
+}
+
 #[derive(Eq, PartialEq)]
-struct Vertex<T: Eq + PartialEq> {
+struct Vertex<T: Eq + PartialEq, L: Ord + Clone> {
     label: usize,
     value: T,
-    neighbors: Vec<usize>,
+    // flat list of (edge label, target) pairs in insertion order, for stable iteration
+    neighbors: Vec<(L, usize)>,
+    // target vertex -> set of labels on edges to it
+    by_target: BTreeMap<usize, BTreeSet<L>>,
+    // label -> set of target vertices reachable by an edge with that label
+    by_label: BTreeMap<L, BTreeSet<usize>>,
+    // labels of vertices with a direct edge into this vertex
+    predecessors: BTreeSet<usize>,
 }
 
-impl<T: Eq + PartialEq> Vertex<T> {
+impl<T: Eq + PartialEq, L: Ord + Clone> Vertex<T, L> {
 
-    // add a neighbor to the vertex
-    // returns true if added, false if the neighbor was already a neighbor
-    fn add_neighbor(&mut self, label: usize) -> bool {
-        if !self.has_neighbor(&label) {
-            self.neighbors.push(label);
-            return true
+    // add a neighbor reachable by the given labelled edge
+    // returns true if added, false if this exact (target, label) edge already existed
+    fn add_neighbor(&mut self, target: usize, edge_label: L) -> bool {
+        if self.by_target.get(&target).map_or(false, |labels| labels.contains(&edge_label)) {
+            return false
         }
-        false
+
+        self.by_target.entry(target).or_insert_with(BTreeSet::new).insert(edge_label.clone());
+        self.by_label.entry(edge_label.clone()).or_insert_with(BTreeSet::new).insert(target);
+        self.neighbors.push((edge_label, target));
+        true
     }
 
-    // returns true if the vertex has a given neighbor
-    fn has_neighbor(&self, neighbor: &usize) -> bool {
-        self.neighbors.contains(neighbor)
+    // returns true if the vertex has an edge (of any label) to the given target
+    fn has_neighbor(&self, target: &usize) -> bool {
+        self.by_target.contains_key(target)
     }
 
-    // deletes the given neighbor of the vertex
-    // returns true if the neighbor was removed, false if it was not a neighbor originally
-    fn delete_neighbor(&mut self, neighbor: &usize) -> bool {
-        for i in 0..self.neighbors.len() {
-            if self.neighbors.get(i).unwrap() == neighbor {
-                self.neighbors.remove(i);
-                return true
+    // deletes all edges (of any label) to the given target
+    // returns true if at least one edge was removed, false if there was none
+    fn delete_neighbor(&mut self, target: &usize) -> bool {
+        if let Some(labels) = self.by_target.remove(target) {
+            for label in &labels {
+                if let Some(targets) = self.by_label.get_mut(label) {
+                    targets.remove(target);
+                    if targets.is_empty() {
+                        self.by_label.remove(label);
+                    }
+                }
             }
+            self.neighbors.retain(|(_, neighbor)| neighbor != target);
+            true
+        } else {
+            false
         }
-        false
     }
 
-    // returns the number of neighbors of the vertex
+    // returns the number of labelled edges leaving the vertex
     fn num_neighbors(&self) -> usize {
         self.neighbors.len()
     }
@@ -274,7 +451,7 @@ mod tests {
 
     #[test]
     fn test_empty_graph() {
-        let graph: Graph<&str> = Graph::new();
+        let graph: Graph<&str, ()> = Graph::new();
         assert_eq!(0, graph.num_vertices());
         assert_eq!(0, graph.num_edges());
         assert_eq!(false, graph.has_cycle())
@@ -282,7 +459,7 @@ mod tests {
 
     #[test]
     fn test_basic_graph() -> Result<(), GraphError>{
-        let mut graph = Graph::new();
+        let mut graph: Graph<&str, ()> = Graph::new();
 
         let hello  = graph.add_vertex("Hello");
         assert_eq!(1, graph.num_vertices());
@@ -290,7 +467,7 @@ mod tests {
         assert_eq!("Hello", graph.get_vertex(&hello).unwrap().borrow().value);
 
         let world = graph.add_vertex("World");
-        graph.add_edge(&hello, world)?;
+        graph.add_edge(&hello, world, ())?;
         assert_eq!(1, graph.num_edges());
         assert!(graph.has_edge(&hello, &world));
         graph.delete_edge(&hello, &world)?;
@@ -298,7 +475,7 @@ mod tests {
         assert_eq!(0, graph.num_edges());
 
         let delete = graph.add_vertex("nope");
-        graph.add_edge(&world, delete)?;
+        graph.add_edge(&world, delete, ())?;
         let deleted_value = graph.delete_vertex(&delete);
         assert_eq!("nope", deleted_value?);
         assert_eq!(2, graph.num_vertices());
@@ -308,17 +485,17 @@ mod tests {
 
     #[test]
     fn test_has_cycle() -> Result<(), GraphError>{
-        let mut graph = Graph::new();
+        let mut graph: Graph<&str, ()> = Graph::new();
         assert_eq!(false, graph.has_cycle());
 
         let hello  = graph.add_vertex("Hello");
         assert_eq!(false, graph.has_cycle());
 
         let world = graph.add_vertex("World");
-        graph.add_edge(&hello, world)?;
+        graph.add_edge(&hello, world, ())?;
         assert_eq!(false, graph.has_cycle());
 
-        graph.add_edge(&world, hello)?;
+        graph.add_edge(&world, hello, ())?;
         assert_eq!(true, graph.has_cycle());
 
         graph.delete_edge(&world, &hello)?;
@@ -327,11 +504,133 @@ mod tests {
         let something = graph.add_vertex("Something");
         let something_else = graph.add_vertex("Else");
 
-        graph.add_edge(&world, something)?;
-        graph.add_edge(&something, something_else)?;
+        graph.add_edge(&world, something, ())?;
+        graph.add_edge(&something, something_else, ())?;
 
-        graph.add_edge(&something_else, world)?;
+        graph.add_edge(&something_else, world, ())?;
         assert_eq!(true, graph.has_cycle());
         Ok(())
     }
+
+    #[test]
+    fn test_labelled_edges() -> Result<(), GraphError> {
+        let mut graph: Graph<&str, &str> = Graph::new();
+
+        let hello = graph.add_vertex("Hello");
+        let world = graph.add_vertex("World");
+        let there = graph.add_vertex("There");
+
+        graph.add_edge(&hello, world, "greeting")?;
+        graph.add_edge(&hello, there, "greeting")?;
+        graph.add_edge(&hello, world, "other")?;
+
+        // two distinct labelled edges between hello and world
+        assert_eq!(3, graph.num_edges());
+        assert!(graph.has_edge(&hello, &world));
+
+        let mut greeting_children: Vec<usize> = graph.find_children_with_label(&hello, &"greeting").collect();
+        greeting_children.sort();
+        let mut expected_children = vec![world, there];
+        expected_children.sort();
+        assert_eq!(expected_children, greeting_children);
+
+        let mut labels: Vec<&str> = graph.edge_labels(&hello, &world).collect();
+        labels.sort();
+        assert_eq!(vec!["greeting", "other"], labels);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_revision_bumps_on_mutation() -> Result<(), GraphError> {
+        let mut graph: Graph<&str, ()> = Graph::new();
+        assert_eq!(0, graph.revision());
+
+        let hello = graph.add_vertex("Hello");
+        let world = graph.add_vertex("World");
+        assert_eq!(2, graph.revision());
+
+        graph.add_edge(&hello, world, ())?;
+        assert_eq!(3, graph.revision());
+
+        // adding the same edge again is a no-op, so the revision should not bump
+        graph.add_edge(&hello, world, ())?;
+        assert_eq!(3, graph.revision());
+
+        graph.delete_edge(&hello, &world)?;
+        assert_eq!(4, graph.revision());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_predecessors() -> Result<(), GraphError> {
+        let mut graph: Graph<&str, ()> = Graph::new();
+
+        let hello = graph.add_vertex("Hello");
+        let world = graph.add_vertex("World");
+        let there = graph.add_vertex("There");
+
+        graph.add_edge(&hello, there, ())?;
+        graph.add_edge(&world, there, ())?;
+
+        let mut predecessors: Vec<usize> = graph.predecessors(&there).collect();
+        predecessors.sort();
+        let mut expected = vec![hello, world];
+        expected.sort();
+        assert_eq!(expected, predecessors);
+
+        graph.delete_vertex(&hello)?;
+        let predecessors: Vec<usize> = graph.predecessors(&there).collect();
+        assert_eq!(vec![world], predecessors);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_graph_builder() -> Result<(), GraphError> {
+        let mut builder: GraphBuilder<&str, ()> = GraphBuilder::new();
+
+        let hello = builder.with_vertex("Hello");
+        let world = builder.with_vertex("World");
+        builder.with_edge(hello, world);
+
+        let graph = builder.build()?;
+        assert_eq!(2, graph.num_vertices());
+        assert_eq!(1, graph.num_edges());
+        assert!(graph.has_edge(&hello, &world));
+        Ok(())
+    }
+
+    #[test]
+    fn test_graph_builder_rejects_self_loop() {
+        let mut builder: GraphBuilder<&str, ()> = GraphBuilder::new();
+        let hello = builder.with_vertex("Hello");
+        builder.with_edge(hello, hello);
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn test_graph_builder_rejects_unknown_label() {
+        let mut builder: GraphBuilder<&str, ()> = GraphBuilder::new();
+        let hello = builder.with_vertex("Hello");
+        builder.with_edge(hello, hello + 1);
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn test_to_dot() -> Result<(), GraphError> {
+        let mut graph: Graph<&str, &str> = Graph::new();
+
+        let hello = graph.add_vertex("Hello");
+        let world = graph.add_vertex("World");
+        graph.add_edge(&hello, world, "greeting")?;
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph {\n    rankdir=LR;\n"));
+        assert!(dot.contains(&format!("{} [label=\"Hello\"];", hello)));
+        assert!(dot.contains(&format!("{} [label=\"World\"];", world)));
+        assert!(dot.contains(&format!("{} -> {} [label=\"greeting\"];", hello, world)));
+        Ok(())
+    }
 }
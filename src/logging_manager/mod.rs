@@ -1,35 +1,104 @@
 mod time_manager;
 
-use std::fs::{create_dir, read_dir, remove_file, DirEntry, OpenOptions};
-use std::io::{Error, Write};
-use std::path::Path;
+use std::fs::{create_dir, read_dir, remove_file, rename, DirEntry, OpenOptions};
+use std::io::{self, Error, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::thread;
 use log::{Metadata, Record, Level, SetLoggerError, LevelFilter};
+use serde::{Deserialize, Deserializer};
 
 /// Struct holding sender to dedicated logging thread
 pub struct LoggingService {
     sender: mpsc::SyncSender<LoggingCommands>,
+    level: LevelFilter,
 }
 
-// struct holding the body of a message to log
+// struct holding the structured body of a message to log; the writer formats these fields
+// according to the configured LogFormat rather than receiving an already-formatted string
 struct MessageBody {
-    level: String,
-    content: String,
+    level: Level,
+    timestamp: String,
+    message: String,
+    target: String,
 }
 
 // commands that can be sent to the logging service
 enum LoggingCommands {
     Kill,
     Message(MessageBody),
+    // acknowledges, via the given sender, once every command queued ahead of this one has been
+    // fully processed
+    Flush(mpsc::Sender<()>),
 }
 
-/// Configuration struct for Logging service
+/// The on-disk format for log records.
+#[derive(Deserialize)]
+pub enum LogFormat {
+    /// `[timestamp] LEVEL message`, one record per line
+    Plain,
+    /// One JSON object per line, Bunyan-style: `{"time": "...", "level": "...", "msg": "...", "module": "..."}`
+    Json,
+}
+
+/// Where a log record should be written. More than one destination may be configured at once;
+/// every configured destination receives every record.
+#[derive(Deserialize)]
+pub enum LogDestination {
+    /// Colorized by level when stdout is a TTY and the format is Plain, uncolored otherwise
+    Stdout,
+    /// Colorized by level when stderr is a TTY and the format is Plain, uncolored otherwise
+    Stderr,
+    /// Dated, size-rotated files in the given directory
+    File(PathBuf),
+}
+
+/// Configuration struct for Logging service. Can be loaded directly from a TOML block.
+#[derive(Deserialize)]
 pub struct LoggingConfig {
-    /// Path from executable to directory to be used for log files
-    pub logging_directory: &'static Path,
-    /// The maximum size of the logging directory in bytes
+    /// The set of sinks every log record is written to
+    pub destinations: Vec<LogDestination>,
+    /// The maximum size of a logging directory in bytes (only applies to `File` destinations)
     pub max_dir_size: usize,
+    /// The maximum size of a single day's current log file in bytes, before it is rotated to a
+    /// numbered archive (only applies to `File` destinations)
+    pub max_file_size: usize,
+    /// The on-disk format for log records
+    pub format: LogFormat,
+    /// The maximum logging level that will be recorded; messages above this level are dropped
+    /// Note: The order of logging levels (decreasing) is: Trace, Debug, Info, Warn, Error.
+    /// Therefore, specifying Debug as the level will ignore Trace messages.
+    #[serde(deserialize_with = "deserialize_level_filter")]
+    pub level: LevelFilter,
+}
+
+// mirrors log::LevelFilter for deserialization: the log crate only implements Deserialize for
+// LevelFilter itself when its non-default "serde" feature is enabled
+#[derive(Deserialize)]
+enum LevelFilterConfig {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LevelFilterConfig> for LevelFilter {
+    fn from(value: LevelFilterConfig) -> Self {
+        match value {
+            LevelFilterConfig::Off => LevelFilter::Off,
+            LevelFilterConfig::Error => LevelFilter::Error,
+            LevelFilterConfig::Warn => LevelFilter::Warn,
+            LevelFilterConfig::Info => LevelFilter::Info,
+            LevelFilterConfig::Debug => LevelFilter::Debug,
+            LevelFilterConfig::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+fn deserialize_level_filter<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LevelFilter, D::Error> {
+    LevelFilterConfig::deserialize(deserializer).map(LevelFilter::from)
 }
 
 impl LoggingService {
@@ -42,6 +111,7 @@ impl LoggingService {
     ///
     pub fn new(options: LoggingConfig) -> LoggingService {
         let (sender, receiver) = mpsc::sync_channel(1);
+        let level = options.level;
 
         // kick off logging thread
         thread::spawn(move || loop {
@@ -50,30 +120,31 @@ impl LoggingService {
                     log(message, &options)
                         .expect("Logging service failed when receiving message.");
                 }
+                LoggingCommands::Flush(ack) => {
+                    // commands before this one have already been fully processed by the time
+                    // this arm runs, since the thread handles them one at a time in order
+                    let _ = ack.send(());
+                }
                 LoggingCommands::Kill => break,
             }
         });
 
-        LoggingService { sender }
+        LoggingService { sender, level }
 
     }
 
-    /// Initiate global logger by boxing the service and sending it to the global logger.
-    ///
-    /// # Arguments
-    ///
-    /// * `max_logging_level` - LevelFilter representing the max logging level for the logging service.
-    /// Note: The order of logging levels (decreasing) is: Trace, Debug, Info, Warn, Error.
-    /// Therefore, specifying Debug as the max logging level will ignore Trace logging messages.
-    ///
-    pub fn init(self, max_logging_level: LevelFilter) -> Result<(), SetLoggerError> {
+    /// Initiate global logger by boxing the service and sending it to the global logger. The
+    /// configured LoggingConfig's `level` becomes the global max logging level.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+
+        let level = self.level;
 
         // box logger
         let logger = Box::new(self);
 
         // set global logger
         log::set_boxed_logger(logger)
-            .map(|()| log::set_max_level(max_logging_level))?;
+            .map(|()| log::set_max_level(level))?;
 
         Ok(())
     }
@@ -90,47 +161,107 @@ impl Drop for LoggingService {
 
 impl log::Log for LoggingService {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= Level::Info
+        metadata.level() <= self.level
     }
 
     fn log(&self, record: &Record) {
-
-        // convert level to string
-        let level = match record.level() {
-            Level::Error => String::from(" ERROR "),
-            Level::Debug => String::from(" DEBUG "),
-            Level::Info => String::from(" INFO  "),
-            Level::Trace => String::from(" TRACE "),
-            Level::Warn => String::from(" WARN  "),
-        };
-
         self.sender
-            .send(LoggingCommands::Message(MessageBody { content: record.args().to_string(), level}))
+            .send(LoggingCommands::Message(MessageBody {
+                level: record.level(),
+                timestamp: time_manager::curr_timestamp(),
+                message: record.args().to_string(),
+                target: record.target().to_string(),
+            }))
             .expect("Failed to send message to logging service.");
     }
 
     fn flush(&self) {
-        unimplemented!()
+        let (ack_sender, ack_receiver) = mpsc::channel();
+        self.sender
+            .send(LoggingCommands::Flush(ack_sender))
+            .expect("Failed to send flush command to logging service.");
+        ack_receiver
+            .recv()
+            .expect("Logging service dropped flush acknowledgement channel.");
     }
 }
 
-// write a message to a log file
-// writes the given message to a log file for the current date in the logging directory
-// a file will be created in the logging directory specified by the logging config containing the message
-// the file will be titled with the current unix date in the format "YYYY_MM_DD.log"
-// the message will be preceded with a unix timestamp in the format "[YYYY-MM-DD HH:MM:SS]"
+// dispatches the given message to every destination configured in options
+// file-rotation logic only runs for File destinations; terminal destinations are colorized
+// by level when the underlying stream is a TTY and the format is Plain
 fn log(message_body: MessageBody, options: &LoggingConfig) -> Result<(), Error> {
+    for destination in &options.destinations {
+        match destination {
+            LogDestination::Stdout => {
+                let is_tty = io::stdout().is_terminal();
+                write_terminal(&mut io::stdout(), &message_body, options, is_tty)?;
+            }
+            LogDestination::Stderr => {
+                let is_tty = io::stderr().is_terminal();
+                write_terminal(&mut io::stderr(), &message_body, options, is_tty)?;
+            }
+            LogDestination::File(directory) => write_file(directory, &message_body, options)?,
+        }
+    }
+
+    Ok(())
+}
+
+// writes a message to a terminal stream, colorizing plain-format lines when the stream is a TTY
+// (JSON lines are never colorized, since ANSI escapes would break downstream JSON parsing)
+fn write_terminal(writer: &mut impl Write, message_body: &MessageBody, options: &LoggingConfig, is_tty: bool) -> Result<(), Error> {
+    let line = match options.format {
+        LogFormat::Plain => format_plain(message_body),
+        LogFormat::Json => format_json(message_body),
+    };
+
+    if is_tty {
+        if let LogFormat::Plain = options.format {
+            return writer.write_all(colorize(message_body.level, &line).as_bytes());
+        }
+    }
+
+    writer.write_all(line.as_bytes())
+}
+
+// wraps a formatted line in the ANSI color code for its level, resetting color at the end
+fn colorize(level: Level, line: &str) -> String {
+    let color_code = match level {
+        Level::Error => "31", // red
+        Level::Warn => "33",  // yellow
+        Level::Info => "32",  // green
+        Level::Debug | Level::Trace => "0", // no color, but still pass through the reset below
+    };
+    format!("\x1b[{}m{}\x1b[0m\n", color_code, line.trim_end_matches('\n'))
+}
+
+// writes a message to a log file for the current date in the given directory
+// a file will be created in the directory containing the message
+// the file will be titled with the current date in the format "YYYY_MM_DD.log"
+// if appending the message would grow that file past max_file_size, it is rotated to a numbered
+// archive first
+// the message is formatted according to the configured LogFormat
+fn write_file(directory: &Path, message_body: &MessageBody, options: &LoggingConfig) -> Result<(), Error> {
     // create logging dir if needed
-    if !options.logging_directory.exists() {
-        create_dir(&options.logging_directory)?;
+    if !directory.exists() {
+        create_dir(directory)?;
     } else {
-        check_size(options)?;
+        check_size(directory, options.max_dir_size)?;
     }
 
-    // path to file
-    let log_file_path = options
-        .logging_directory
-        .join(format!("{}.log", time_manager::curr_datestamp()));
+    let datestamp = time_manager::curr_datestamp();
+    let log_file_path = directory.join(format!("{}.log", datestamp));
+
+    let line = match options.format {
+        LogFormat::Plain => format_plain(message_body),
+        LogFormat::Json => format_json(message_body),
+    };
+
+    if let Ok(metadata) = log_file_path.metadata() {
+        if metadata.len() as usize + line.len() > options.max_file_size {
+            rotate_file(directory, &datestamp)?;
+        }
+    }
 
     // create or open
     let mut file = OpenOptions::new()
@@ -138,15 +269,76 @@ fn log(message_body: MessageBody, options: &LoggingConfig) -> Result<(), Error>
         .create(true)
         .open(log_file_path)?;
 
-    // write message
-    file.write_all((time_manager::curr_timestamp() + message_body.level.as_str() + message_body.content.as_str() + "\n").as_bytes())?;
+    file.write_all(line.as_bytes())?;
     file.sync_all()
 }
 
+// moves a day's current log file out of the way into numbered archives, shifting existing
+// "YYYY_MM_DD.N.log" archives up by one so the current file always lands in slot 1
+fn rotate_file(directory: &Path, datestamp: &str) -> Result<(), Error> {
+    // find the highest existing archive number for this date
+    let mut highest = 0;
+    while directory.join(format!("{}.{}.log", datestamp, highest + 1)).exists() {
+        highest += 1;
+    }
+
+    // shift archives up by one, starting from the highest so renames never collide
+    for suffix in (1..=highest).rev() {
+        rename(
+            directory.join(format!("{}.{}.log", datestamp, suffix)),
+            directory.join(format!("{}.{}.log", datestamp, suffix + 1)),
+        )?;
+    }
+
+    rename(
+        directory.join(format!("{}.log", datestamp)),
+        directory.join(format!("{}.1.log", datestamp)),
+    )
+}
+
+// formats a message body as "[YYYY-MM-DD HH:MM:SS] LEVEL message"
+fn format_plain(message_body: &MessageBody) -> String {
+    let level = match message_body.level {
+        Level::Error => " ERROR ",
+        Level::Debug => " DEBUG ",
+        Level::Info => " INFO  ",
+        Level::Trace => " TRACE ",
+        Level::Warn => " WARN  ",
+    };
+    format!("{}{}{}\n", message_body.timestamp, level, message_body.message)
+}
+
+// formats a message body as one Bunyan-style JSON object
+fn format_json(message_body: &MessageBody) -> String {
+    format!(
+        "{{\"time\": \"{}\", \"level\": \"{}\", \"msg\": \"{}\", \"module\": \"{}\"}}\n",
+        escape_json(&message_body.timestamp),
+        message_body.level,
+        escape_json(&message_body.message),
+        escape_json(&message_body.target),
+    )
+}
+
+// escapes a string for embedding as a JSON string value
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 // checks the size of the directory, deleting oldest files if too big
-fn check_size(options: &LoggingConfig) -> Result<(), Error> {
+fn check_size(directory: &Path, max_dir_size: usize) -> Result<(), Error> {
     // get sorted Vec of DirEntries
-    let files = get_sorted_files_from_dir(options.logging_directory)?;
+    let files = get_sorted_files_from_dir(directory)?;
 
     // check size of each file
     let mut total_size: usize = 0;
@@ -156,7 +348,7 @@ fn check_size(options: &LoggingConfig) -> Result<(), Error> {
         total_size += files.get(i).unwrap().metadata()?.len() as usize;
 
         // delete oldest files until size is small enough
-        while total_size > options.max_dir_size && start_index <= i {
+        while total_size > max_dir_size && start_index <= i {
             total_size -= files.get(start_index).unwrap().metadata()?.len() as usize;
             remove_file(files.get(start_index).unwrap().path())?;
             start_index += 1;
@@ -167,6 +359,8 @@ fn check_size(options: &LoggingConfig) -> Result<(), Error> {
 }
 
 // gets a sorted list (old to new) of logging files from logging dir
+// recognizes both the current day's file ("YYYY_MM_DD.log") and its rotated archives
+// ("YYYY_MM_DD.N.log"), ordering archives oldest-first (highest N) with the current file last
 fn get_sorted_files_from_dir(logging_directory: &Path) -> Result<Vec<DirEntry>, Error> {
     // files to be sorted
     let mut files: Vec<DirEntry> = Vec::new();
@@ -184,18 +378,45 @@ fn get_sorted_files_from_dir(logging_directory: &Path) -> Result<Vec<DirEntry>,
             };
 
             // check filename
-            if filename.ends_with(".log") && time_manager::check_date(&filename[0..10]) {
+            if parse_log_filename(&filename).is_some() {
                 files.push(file);
             }
         }
     }
 
-    // sort files by date
-    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    // sort by date, then within a date by archive number descending (current file, archive
+    // number 0, sorts last since it is always the newest)
+    files.sort_by_key(|file| {
+        let filename = file.file_name().into_string().unwrap();
+        let (date, suffix) = parse_log_filename(&filename).unwrap();
+        (date, -(suffix as i64))
+    });
 
     Ok(files)
 }
 
+// parses a logging filename into its date and archive number ("YYYY_MM_DD.log" has archive
+// number 0, being the current file; "YYYY_MM_DD.N.log" has archive number N)
+fn parse_log_filename(filename: &str) -> Option<(String, usize)> {
+    if filename.len() < 14 || !filename.ends_with(".log") {
+        return None;
+    }
+
+    let date = &filename[0..10];
+    if !time_manager::check_date(date) {
+        return None;
+    }
+
+    let middle = &filename[10..filename.len() - 4];
+    let suffix = match middle.strip_prefix('.') {
+        Some(suffix) => suffix.parse().ok()?,
+        None if middle.is_empty() => 0,
+        None => return None,
+    };
+
+    Some((date.to_string(), suffix))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,14 +427,24 @@ mod tests {
     use std::io::{BufReader, BufRead};
     use log::*;
 
+    // logs a message through the LoggingService's log::Log::log implementation directly,
+    // without registering it as the global logger (log::set_boxed_logger is a process-wide
+    // call-once singleton, so only one test in this module may call LoggingService::init)
+    fn log_message(service: &LoggingService, level: Level, target: &str, message: &str) {
+        service.log(&Record::builder().level(level).target(target).args(format_args!("{}", message)).build());
+    }
+
     #[test]
     fn test_log() -> Result<(), Box<dyn Error>> {
         let logging_directory = Path::new("./test_logs/");
         let logging_service = LoggingService::new(LoggingConfig {
-            logging_directory,
+            destinations: vec![LogDestination::File(logging_directory.to_path_buf())],
             max_dir_size: 10000,
+            max_file_size: 10000,
+            format: LogFormat::Plain,
+            level: LevelFilter::Trace,
         });
-        logging_service.init(log::LevelFilter::Trace)?;
+        logging_service.init()?;
         let current_date = time_manager::curr_datestamp();
         let log_file_name = format!("{}.log", current_date);
         let log_file_path_buf = logging_directory
@@ -254,6 +485,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_log_json() -> Result<(), Box<dyn Error>> {
+        let logging_directory = Path::new("./test_logs_json/");
+        let logging_service = LoggingService::new(LoggingConfig {
+            destinations: vec![LogDestination::File(logging_directory.to_path_buf())],
+            max_dir_size: 10000,
+            max_file_size: 10000,
+            format: LogFormat::Json,
+            level: LevelFilter::Trace,
+        });
+        let current_date = time_manager::curr_datestamp();
+        let log_file_path_buf = logging_directory.join(format!("{}.log", current_date));
+        let log_file_path = log_file_path_buf.as_path();
+
+        log_message(&logging_service, Level::Info, "test", "test info");
+
+        // sleep because logging is done on a different thread (and will take time)
+        thread::sleep(time::Duration::from_millis(10));
+
+        let log_file = File::open(log_file_path)?;
+        let mut lines = BufReader::new(log_file).lines();
+        let line = lines.next().unwrap().unwrap();
+        assert!(line.contains("\"level\": \"INFO\""));
+        assert!(line.contains("\"msg\": \"test info\""));
+        assert!(lines.next().is_none());
+
+        remove_dir_all(logging_directory)?;
+        Ok(())
+    }
+
     #[test]
     fn test_check_size() -> Result<(), std::io::Error> {
 
@@ -262,64 +523,193 @@ mod tests {
         let file2 = Path::new("2020_08_02.log");
         let file3 = Path::new("2020_08_03.log");
         let random_text = "Lorem ipsum dolor sit amet, consectetur adipisicing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
-        let config1 = LoggingConfig {
-            logging_directory: Path::new("./test_logs2/"),
-            max_dir_size: 0
-        };
-        let config2 = LoggingConfig {
-            logging_directory: Path::new("./test_logs3/"),
-            max_dir_size: 1000
-        };
-        let config3 = LoggingConfig {
-            logging_directory: Path::new("./test_logs4/"),
-            max_dir_size: 10000
-        };
-
-        // config 1: max dir size is 0, all files should be deleted
-        create_dir(&config1.logging_directory)?;
-        let mut file = File::create(&config1.logging_directory.join(&file1))?;
+        let directory1 = Path::new("./test_logs2/");
+        let directory2 = Path::new("./test_logs3/");
+        let directory3 = Path::new("./test_logs4/");
+
+        // directory 1: max dir size is 0, all files should be deleted
+        create_dir(directory1)?;
+        let mut file = File::create(directory1.join(&file1))?;
         file.write_all(random_text.as_bytes())?;
-        file = File::create(&config1.logging_directory.join(&file2))?;
+        file = File::create(directory1.join(&file2))?;
         file.write_all(random_text.as_bytes())?;
-        file = File::create(&config1.logging_directory.join(&file3))?;
+        file = File::create(directory1.join(&file3))?;
         file.write_all(random_text.as_bytes())?;
         file.sync_all()?;
-        check_size(&config1)?;
-        remove_dir(&config1.logging_directory)?;
+        check_size(directory1, 0)?;
+        remove_dir(directory1)?;
 
-        // config 2: max dir size is 1000 bytes, first file should be deleted (second and third remains)
-        create_dir(&config2.logging_directory)?;
-        let mut file = File::create(&config2.logging_directory.join(&file1))?;
+        // directory 2: max dir size is 1000 bytes, first file should be deleted (second and third remains)
+        create_dir(directory2)?;
+        let mut file = File::create(directory2.join(&file1))?;
         file.write_all(random_text.as_bytes())?;
-        file = File::create(&config2.logging_directory.join(&file2))?;
+        file = File::create(directory2.join(&file2))?;
         file.write_all(random_text.as_bytes())?;
-        file = File::create(&config2.logging_directory.join(&file3))?;
+        file = File::create(directory2.join(&file3))?;
         file.write_all(random_text.as_bytes())?;
         file.sync_all()?;
-        check_size(&config2)?;
-        assert_eq!(false, config2.logging_directory.join(&file1).exists());
-        assert!(config2.logging_directory.join(&file2).exists());
-        assert!(config2.logging_directory.join(&file3).exists());
-        remove_dir_all(&config2.logging_directory)?;
-
-        // config 3: max dir size is huge, no files should be deleted
-        create_dir(&config3.logging_directory)?;
-        let mut file = File::create(&config3.logging_directory.join(&file1))?;
+        check_size(directory2, 1000)?;
+        assert_eq!(false, directory2.join(&file1).exists());
+        assert!(directory2.join(&file2).exists());
+        assert!(directory2.join(&file3).exists());
+        remove_dir_all(directory2)?;
+
+        // directory 3: max dir size is huge, no files should be deleted
+        create_dir(directory3)?;
+        let mut file = File::create(directory3.join(&file1))?;
         file.write_all(random_text.as_bytes())?;
-        file = File::create(&config3.logging_directory.join(&file2))?;
+        file = File::create(directory3.join(&file2))?;
         file.write_all(random_text.as_bytes())?;
-        file = File::create(&config3.logging_directory.join(&file3))?;
+        file = File::create(directory3.join(&file3))?;
         file.write_all(random_text.as_bytes())?;
         file.sync_all()?;
-        check_size(&config3)?;
-        assert!(config3.logging_directory.join(&file1).exists());
-        assert!(config3.logging_directory.join(&file2).exists());
-        assert!(config3.logging_directory.join(&file3).exists());
-        remove_dir_all(&config3.logging_directory)?;
+        check_size(directory3, 10000)?;
+        assert!(directory3.join(&file1).exists());
+        assert!(directory3.join(&file2).exists());
+        assert!(directory3.join(&file3).exists());
+        remove_dir_all(directory3)?;
 
         Ok(())
     }
 
+    #[test]
+    fn test_rotate_file() -> std::io::Result<()> {
+        let directory = Path::new("./test_logs_rotate/");
+        let datestamp = time_manager::curr_datestamp();
+        create_dir(directory)?;
+
+        // a current file and one existing archive
+        File::create(directory.join(format!("{}.log", datestamp)))?.write_all(b"current")?;
+        File::create(directory.join(format!("{}.1.log", datestamp)))?.write_all(b"oldest")?;
+
+        rotate_file(directory, &datestamp)?;
+
+        // the existing archive shifted up to slot 2, and the current file became slot 1
+        assert_eq!(false, directory.join(format!("{}.log", datestamp)).exists());
+        assert_eq!(b"oldest".to_vec(), std::fs::read(directory.join(format!("{}.2.log", datestamp)))?);
+        assert_eq!(b"current".to_vec(), std::fs::read(directory.join(format!("{}.1.log", datestamp)))?);
+
+        remove_dir_all(directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_rotates_oversized_file() -> Result<(), Box<dyn Error>> {
+        let logging_directory = Path::new("./test_logs_size_rotate/");
+        let logging_service = LoggingService::new(LoggingConfig {
+            destinations: vec![LogDestination::File(logging_directory.to_path_buf())],
+            max_dir_size: 10000,
+            max_file_size: 1,
+            format: LogFormat::Plain,
+            level: LevelFilter::Trace,
+        });
+        let datestamp = time_manager::curr_datestamp();
+
+        log_message(&logging_service, Level::Info, "test", "first message");
+        thread::sleep(time::Duration::from_millis(10));
+        log_message(&logging_service, Level::Info, "test", "second message");
+        thread::sleep(time::Duration::from_millis(10));
+
+        // the first message filled the (tiny) max_file_size, so it was rotated into slot 1
+        // and the second message landed in a fresh current file
+        assert!(directory_contains_message(logging_directory, &format!("{}.1.log", datestamp), "first message")?);
+        assert!(directory_contains_message(logging_directory, &format!("{}.log", datestamp), "second message")?);
+
+        remove_dir_all(logging_directory)?;
+        Ok(())
+    }
+
+    fn directory_contains_message(directory: &Path, filename: &str, message: &str) -> std::io::Result<bool> {
+        let contents = std::fs::read_to_string(directory.join(filename))?;
+        Ok(contents.contains(message))
+    }
+
+    #[test]
+    fn test_enabled_respects_configured_level() {
+        let logging_service = LoggingService::new(LoggingConfig {
+            destinations: vec![],
+            max_dir_size: 10000,
+            max_file_size: 10000,
+            format: LogFormat::Plain,
+            level: LevelFilter::Warn,
+        });
+
+        let debug_metadata = Metadata::builder().level(Level::Debug).target("test").build();
+        let error_metadata = Metadata::builder().level(Level::Error).target("test").build();
+
+        assert_eq!(false, logging_service.enabled(&debug_metadata));
+        assert!(logging_service.enabled(&error_metadata));
+    }
+
+    #[test]
+    fn test_flush_waits_for_pending_messages() -> Result<(), Box<dyn Error>> {
+        let logging_directory = Path::new("./test_logs_flush/");
+        let logging_service = LoggingService::new(LoggingConfig {
+            destinations: vec![LogDestination::File(logging_directory.to_path_buf())],
+            max_dir_size: 10000,
+            max_file_size: 10000,
+            format: LogFormat::Plain,
+            level: LevelFilter::Trace,
+        });
+        let log_file_path_buf = logging_directory
+            .join(format!("{}.log", time_manager::curr_datestamp()));
+        let log_file_path = log_file_path_buf.as_path();
+
+        log_message(&logging_service, Level::Info, "test", "test flush");
+        logging_service.flush();
+
+        // no sleep: flush() should only return once the message above has already hit disk
+        let log_file = File::open(log_file_path)?;
+        let mut lines = BufReader::new(log_file).lines();
+        assert!(lines.next().unwrap().unwrap().ends_with("] INFO  test flush"));
+
+        remove_dir_all(logging_directory)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_colorize() {
+        let plain = format_plain(&MessageBody {
+            level: Level::Error,
+            timestamp: String::from("[2020-01-01 00:00:00]"),
+            message: String::from("test error"),
+            target: String::from("test"),
+        });
+
+        let colored = colorize(Level::Error, &plain);
+        assert!(colored.starts_with("\x1b[31m"));
+        assert!(colored.ends_with("\x1b[0m\n"));
+        assert!(colored.contains("test error"));
+    }
+
+    #[test]
+    fn test_log_multiple_destinations() -> Result<(), Box<dyn Error>> {
+        let logging_directory = Path::new("./test_logs_multi/");
+        let logging_service = LoggingService::new(LoggingConfig {
+            destinations: vec![LogDestination::Stdout, LogDestination::File(logging_directory.to_path_buf())],
+            max_dir_size: 10000,
+            max_file_size: 10000,
+            format: LogFormat::Plain,
+            level: LevelFilter::Trace,
+        });
+        let log_file_path_buf = logging_directory
+            .join(format!("{}.log", time_manager::curr_datestamp()));
+        let log_file_path = log_file_path_buf.as_path();
+
+        log_message(&logging_service, Level::Info, "test", "test multi destination");
+
+        // sleep because logging is done on a different thread (and will take time)
+        thread::sleep(time::Duration::from_millis(10));
+
+        // the file destination should still receive the message, regardless of the stdout destination
+        let log_file = File::open(log_file_path)?;
+        let mut lines = BufReader::new(log_file).lines();
+        assert!(lines.next().unwrap().unwrap().ends_with("] INFO  test multi destination"));
+
+        remove_dir_all(logging_directory)?;
+        Ok(())
+    }
+
     #[test]
     fn test_sorted_files() -> std::io::Result<()> {
         let curr_date = format!("{}.log", time_manager::curr_datestamp());
@@ -337,4 +727,28 @@ mod tests {
         assert_eq!(false, logging_directory.exists());
         Ok(())
     }
+
+    #[test]
+    fn test_sorted_files_orders_numbered_archives_oldest_first() -> std::io::Result<()> {
+        let datestamp = "2020_08_01";
+        // archive 2 is oldest, archive 1 is second oldest, the current file is newest
+        let files = [
+            format!("{}.2.log", datestamp),
+            format!("{}.1.log", datestamp),
+            format!("{}.log", datestamp),
+        ];
+        let logging_directory = Path::new("./test_logs5/");
+        create_dir(logging_directory)?;
+        for filename in files.iter() {
+            File::create(logging_directory.join(filename).as_path())?;
+        }
+
+        let sorted = get_sorted_files_from_dir(logging_directory)?;
+        for i in 0..files.len() {
+            assert_eq!(files[i], sorted[i].file_name().to_str().unwrap());
+        }
+
+        remove_dir_all(logging_directory)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,86 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// returns the current time (UTC) formatted as "[YYYY-MM-DD HH:MM:SS]"
+pub fn curr_timestamp() -> String {
+    let (year, month, day, hour, minute, second) = now_utc();
+    format!("[{:04}-{:02}-{:02} {:02}:{:02}:{:02}]", year, month, day, hour, minute, second)
+}
+
+// returns the current date (UTC) formatted as "YYYY_MM_DD"
+pub fn curr_datestamp() -> String {
+    let (year, month, day, _, _, _) = now_utc();
+    format!("{:04}_{:02}_{:02}", year, month, day)
+}
+
+// checks whether `date` is a well-formed "YYYY_MM_DD" datestamp
+pub fn check_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'_' || bytes[7] != b'_' {
+        return false;
+    }
+
+    if !date[0..4].bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    match (date[5..7].parse::<u32>(), date[8..10].parse::<u32>()) {
+        (Ok(month), Ok(day)) => (1..=12).contains(&month) && (1..=31).contains(&day),
+        _ => false,
+    }
+}
+
+// splits the current UTC time into (year, month, day, hour, minute, second)
+fn now_utc() -> (i64, u32, u32, u32, u32, u32) {
+    let total_seconds = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+    let days = total_seconds.div_euclid(86400);
+    let secs_of_day = total_seconds.rem_euclid(86400);
+
+    let hour = (secs_of_day / 3600) as u32;
+    let minute = ((secs_of_day % 3600) / 60) as u32;
+    let second = (secs_of_day % 60) as u32;
+
+    let (year, month, day) = civil_from_days(days);
+    (year, month, day, hour, minute, second)
+}
+
+// converts a day count since the Unix epoch into a (year, month, day) civil date
+// http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days_since_epoch: i64) -> (i64, u32, u32) {
+    let z = days_since_epoch + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let year_of_era = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = if month <= 2 { year_of_era + 1 } else { year_of_era };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_date_accepts_well_formed_dates() {
+        assert!(check_date("2020_08_01"));
+        assert!(check_date("1999_12_31"));
+    }
+
+    #[test]
+    fn test_check_date_rejects_malformed_dates() {
+        assert!(!check_date("2020-08-01"));
+        assert!(!check_date("2020_13_01"));
+        assert!(!check_date("2020_08_00"));
+        assert!(!check_date("not_a_date"));
+        assert!(!check_date("2020_08_1"));
+    }
+
+    #[test]
+    fn test_civil_from_days_matches_known_epoch_dates() {
+        assert_eq!((1970, 1, 1), civil_from_days(0));
+        assert_eq!((2000, 2, 29), civil_from_days(11016));
+    }
+}
@@ -1,3 +1,9 @@
+mod graph;
+mod logging_manager;
+mod nfa;
+mod templating_manager;
+mod viz;
+
 use std::collections::HashMap;
 use std::fs;
 use std::io::Error;
@@ -11,16 +17,20 @@ use my_http::server::{Config, Router};
 use my_http::server::ListenerResult::SendResponseArc;
 
 fn main() -> Result<(), Error> {
+    let live_graph: Arc<RwLock<graph::Graph<String, String>>> = Arc::new(RwLock::new(graph::Graph::new()));
+
+    let mut router = Router::new();
+    file_router(&mut router, "./web/");
+    graph_router(&mut router, Arc::clone(&live_graph));
+
     server::listen_http(Config {
         addr: "0.0.0.0:80",
         connection_handler_threads: 5,
-        router: file_router("./web/"),
+        router,
     })
 }
 
-fn file_router(directory: &'static str) -> Router {
-    let mut router = Router::new();
-
+fn file_router(router: &mut Router, directory: &'static str) {
     let cache: RwLock<HashMap<String, Arc<Response>>> = RwLock::new(HashMap::new());
 
     router.on_prefix("", move |uri, _| {
@@ -41,8 +51,46 @@ fn file_router(directory: &'static str) -> Router {
 
         SendResponseArc(response)
     });
+}
+
+// serves a live view of the given graph, rendered on demand from its current state
+// the rendered response is cached alongside the graph revision and format (DOT vs SVG) it was
+// built from, so repeat requests are served without re-serializing until the graph actually
+// changes, without mixing up responses between the two formats
+fn graph_router(router: &mut Router, live_graph: Arc<RwLock<graph::Graph<String, String>>>) {
+    let cache: RwLock<Option<(usize, bool, Arc<Response>)>> = RwLock::new(None);
+
+    router.on_prefix("/graph", move |uri, _| {
+        let graph = live_graph.read().unwrap();
+        let revision = graph.revision();
+        let is_svg = uri.ends_with(".svg");
+
+        if let Some((cached_revision, cached_is_svg, response)) = cache.read().unwrap().as_ref() {
+            if *cached_revision == revision && *cached_is_svg == is_svg {
+                return SendResponseArc(Arc::clone(response));
+            }
+        }
+
+        let response = Arc::new(graph_response(&graph, uri));
+        cache.write().unwrap().replace((revision, is_svg, Arc::clone(&response)));
+
+        SendResponseArc(response)
+    });
+}
+
+fn graph_response(live_graph: &graph::Graph<String, String>, uri: &str) -> Response {
+    let (body, content_type) = if uri.ends_with(".svg") {
+        (viz::to_svg(live_graph), "image/svg+xml")
+    } else {
+        (live_graph.to_dot(), "text/vnd.graphviz")
+    };
+
+    let headers = header_map![
+        (header::CONTENT_LENGTH, body.len().to_string()),
+        (header::CONTENT_TYPE, content_type)
+    ];
 
-    router
+    Response { status: status::OK, headers, body: body.into_bytes() }
 }
 
 fn file_response(file_path: &str) -> Response {
@@ -0,0 +1,236 @@
+use crate::graph::Graph;
+
+/// A regular expression over an alphabet of symbols `S`, ready for Thompson's construction.
+pub enum Regex<S> {
+    Literal(S),
+    Concat(Box<Regex<S>>, Box<Regex<S>>),
+    Alternation(Box<Regex<S>>, Box<Regex<S>>),
+    Star(Box<Regex<S>>),
+}
+
+// edge label for an NFA: either an input symbol or the distinguished epsilon (null) marker
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord)]
+pub enum NfaLabel<S: Ord + Clone> {
+    Symbol(S),
+    Epsilon,
+}
+
+/// A compiled NFA: a labelled Graph of states plus the designated start and accept states.
+pub struct Nfa<S: Ord + Clone> {
+    pub graph: Graph<(), NfaLabel<S>>,
+    pub start: usize,
+    pub accept: usize,
+}
+
+// a partially-built fragment of an NFA, with its own start and accept state
+struct Fragment {
+    start: usize,
+    accept: usize,
+}
+
+/// Compiles a regex into an NFA via Thompson's construction.
+pub fn compile<S: Ord + Clone>(regex: &Regex<S>) -> Nfa<S> {
+    let mut graph = Graph::new();
+    let fragment = compile_fragment(&mut graph, regex);
+    Nfa { graph, start: fragment.start, accept: fragment.accept }
+}
+
+fn compile_fragment<S: Ord + Clone>(graph: &mut Graph<(), NfaLabel<S>>, regex: &Regex<S>) -> Fragment {
+    match regex {
+        // a literal symbol becomes two states joined by one symbol-labelled edge
+        Regex::Literal(symbol) => {
+            let start = graph.add_vertex(());
+            let accept = graph.add_vertex(());
+            graph.add_edge(&start, accept, NfaLabel::Symbol(symbol.clone())).unwrap();
+            Fragment { start, accept }
+        }
+
+        // concatenation links the accept state of the left fragment to the start of the right with a null edge
+        Regex::Concat(left, right) => {
+            let left = compile_fragment(graph, left);
+            let right = compile_fragment(graph, right);
+            graph.add_edge(&left.accept, right.start, NfaLabel::Epsilon).unwrap();
+            Fragment { start: left.start, accept: right.accept }
+        }
+
+        // alternation adds a fresh start with two null edges into the branches and a fresh accept
+        // with null edges out of them
+        Regex::Alternation(left, right) => {
+            let left = compile_fragment(graph, left);
+            let right = compile_fragment(graph, right);
+            let start = graph.add_vertex(());
+            let accept = graph.add_vertex(());
+            graph.add_edge(&start, left.start, NfaLabel::Epsilon).unwrap();
+            graph.add_edge(&start, right.start, NfaLabel::Epsilon).unwrap();
+            graph.add_edge(&left.accept, accept, NfaLabel::Epsilon).unwrap();
+            graph.add_edge(&right.accept, accept, NfaLabel::Epsilon).unwrap();
+            Fragment { start, accept }
+        }
+
+        // Kleene star wraps a fragment with null edges forming the loop and the skip
+        Regex::Star(inner) => {
+            let inner = compile_fragment(graph, inner);
+            let start = graph.add_vertex(());
+            let accept = graph.add_vertex(());
+            graph.add_edge(&start, inner.start, NfaLabel::Epsilon).unwrap();
+            graph.add_edge(&start, accept, NfaLabel::Epsilon).unwrap();
+            graph.add_edge(&inner.accept, inner.start, NfaLabel::Epsilon).unwrap();
+            graph.add_edge(&inner.accept, accept, NfaLabel::Epsilon).unwrap();
+            Fragment { start, accept }
+        }
+    }
+}
+
+/// An NFA-shaped graph with null edges eliminated: every remaining transition is either a direct
+/// edge in `graph`, or, when a state's closure reaches back into itself (as Kleene-star closures
+/// do), a self-loop recorded in `self_loops` instead, since `Graph::add_edge` categorically
+/// rejects self-loop edges. Use `neighbors` to see both kinds of transition together.
+pub struct ClosedGraph<L: Ord + Clone> {
+    pub graph: Graph<(), L>,
+    self_loops: Vec<Vec<L>>,
+}
+
+impl<L: Ord + Clone> ClosedGraph<L> {
+    /// Returns every (label, target) transition out of `state`, combining `graph`'s edges with
+    /// any self-loop transitions recorded for that state.
+    pub fn neighbors(&self, state: usize) -> impl Iterator<Item=(L, usize)> {
+        let mut neighbors: Vec<(L, usize)> = self.graph.neighbors(&state).collect();
+        neighbors.extend(self.self_loops[state].iter().cloned().map(move |label| (label, state)));
+        neighbors.into_iter()
+    }
+}
+
+/// Produces an equivalent graph with null edges (as decided by `is_null`) eliminated: for every
+/// state, computes the set of states reachable through null edges and splices in direct copies of
+/// the non-null edges leaving those reachable states. Terminates even when null edges form cycles,
+/// since each state's closure walk tracks visited states.
+pub fn null_closure<L: Ord + Clone>(graph: &Graph<(), L>, is_null: impl Fn(&L) -> bool) -> ClosedGraph<L> {
+    let num_states = graph.num_vertices();
+
+    let mut closed: Graph<(), L> = Graph::new();
+    for _ in 0..num_states {
+        closed.add_vertex(());
+    }
+
+    let mut self_loops: Vec<Vec<L>> = vec![Vec::new(); num_states];
+
+    for state in 0..num_states {
+        // DFS over only null-labelled edges to find this state's epsilon-closure
+        let mut visited = vec![false; num_states];
+        let mut stack = vec![state];
+        let mut reachable = Vec::new();
+        visited[state] = true;
+        while let Some(current) = stack.pop() {
+            reachable.push(current);
+            for (label, target) in graph.neighbors(&current) {
+                if is_null(&label) && !visited[target] {
+                    visited[target] = true;
+                    stack.push(target);
+                }
+            }
+        }
+
+        // splice in direct copies of the non-null edges leaving every state in the closure;
+        // one that would become a self-loop is recorded separately instead, since
+        // Graph::add_edge rejects those outright (and a state's own closure reaching back into
+        // itself through null edges, as Kleene-star does, is exactly how repetition is represented)
+        for reachable_state in reachable {
+            for (label, target) in graph.neighbors(&reachable_state) {
+                if !is_null(&label) {
+                    if target == state {
+                        self_loops[state].push(label);
+                    } else {
+                        closed.add_edge(&state, target, label).unwrap();
+                    }
+                }
+            }
+        }
+    }
+
+    ClosedGraph { graph: closed, self_loops }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_compile_literal() {
+        let nfa = compile(&Regex::Literal('a'));
+        assert_eq!(2, nfa.graph.num_vertices());
+        assert!(nfa.graph.has_edge(&nfa.start, &nfa.accept));
+    }
+
+    #[test]
+    fn test_compile_concat() {
+        // "ab"
+        let regex = Regex::Concat(Box::new(Regex::Literal('a')), Box::new(Regex::Literal('b')));
+        let nfa = compile(&regex);
+        assert_eq!(4, nfa.graph.num_vertices());
+        assert_ne!(nfa.start, nfa.accept);
+    }
+
+    #[test]
+    fn test_null_closure_skips_epsilon_edges() {
+        // "a*" star fragment: start reaches accept only through null edges, and accept has no
+        // outgoing edges, so after closure no edge between them may carry an epsilon label
+        let nfa = compile(&Regex::Star(Box::new(Regex::Literal('a'))));
+        let closed = null_closure(&nfa.graph, |label| matches!(label, NfaLabel::Epsilon));
+
+        let reachable_symbols: Vec<NfaLabel<char>> = closed.graph.edge_labels(&nfa.start, &nfa.accept).collect();
+        assert!(reachable_symbols.is_empty() || reachable_symbols.iter().all(|label| !matches!(label, NfaLabel::Epsilon)));
+    }
+
+    #[test]
+    fn test_null_closure_preserves_symbol_repetition() {
+        // "a*" should still be able to consume "aa": the inner accept state's closure reaches
+        // back into the inner start state, which has a symbol edge back to accept, so that
+        // state needs a self-loop on 'a' in the closed graph or repetition is lost
+        let nfa = compile(&Regex::Star(Box::new(Regex::Literal('a'))));
+        let closed = null_closure(&nfa.graph, |label| matches!(label, NfaLabel::Epsilon));
+
+        // simulate consuming "aa" from the start state through the closed graph
+        let mut states = vec![nfa.start];
+        for _ in 0..2 {
+            states = states.iter()
+                .flat_map(|&state| closed.neighbors(state))
+                .filter(|(label, _)| matches!(label, NfaLabel::Symbol('a')))
+                .map(|(_, target)| target)
+                .collect();
+            assert!(!states.is_empty(), "consuming 'a' should never dead-end on a Kleene-star loop");
+        }
+
+        // every landed state should still be epsilon-equivalent to the original accept state
+        assert!(states.iter().all(|&state| epsilon_reachable(&nfa.graph, state).contains(&nfa.accept)));
+    }
+
+    // collects every state reachable from `state` via only epsilon edges, including `state` itself
+    fn epsilon_reachable(graph: &Graph<(), NfaLabel<char>>, state: usize) -> HashSet<usize> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![state];
+        visited.insert(state);
+        while let Some(current) = stack.pop() {
+            for (label, target) in graph.neighbors(&current) {
+                if matches!(label, NfaLabel::Epsilon) && visited.insert(target) {
+                    stack.push(target);
+                }
+            }
+        }
+        visited
+    }
+
+    #[test]
+    fn test_null_closure_terminates_on_cycles() {
+        // two states with null edges pointing at each other, forming a cycle
+        let mut graph: Graph<(), ()> = Graph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph.add_edge(&a, b, ()).unwrap();
+        graph.add_edge(&b, a, ()).unwrap();
+
+        let closed = null_closure(&graph, |_| true);
+        assert_eq!(2, closed.graph.num_vertices());
+        assert_eq!(0, closed.graph.num_edges());
+    }
+}
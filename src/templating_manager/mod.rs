@@ -2,7 +2,7 @@ use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::str::{from_utf8, FromStr};
 use std::collections::HashMap;
 
@@ -14,8 +14,10 @@ pub enum ParsingError {
     UTF8EncodingError(std::str::Utf8Error),
     /// A path in a template could not be resolved to a valid path
     MalformedTemplatePath(String),
-    // @TODO Check for this error by looking for cycles in a tree
+    /// A template, directly or transitively, includes itself
     RecursivePath(PathBuf),
+    /// A `{{$name}}` template referenced a name that was not present in the supplied vars
+    UnknownVariable(String),
 }
 
 impl Error for ParsingError {}
@@ -31,6 +33,9 @@ impl Display for ParsingError {
             ParsingError::RecursivePath(path) => {
                 write!(f, "Recursive template path call from {}", path.to_str().unwrap())
             }
+            ParsingError::UnknownVariable(name) => {
+                write!(f, "Unknown template variable: {}", name)
+            }
         }
     }
 }
@@ -56,16 +61,42 @@ impl From<std::str::Utf8Error> for ParsingError {
 /// * `filepath` - Path of the file to begin parsing.
 ///
 pub fn parse(filepath: &Path) -> Result<Vec<u8>, ParsingError> {
+    parse_with_vars(filepath, &HashMap::new())
+}
+
+/// Parse a file for templates and return its processed contents as a vector of bytes, resolving
+/// `{{$name}}` templates from `vars` instead of treating them as file includes.
+///
+/// Templates are expected in one of the following formats:
+/// * {{../relative/path/to/file.extension}} - inlines the contents of another file
+/// * {{$name}} - inlines `vars[name]`, or fails with `ParsingError::UnknownVariable` if absent
+///
+/// A literal `{{` can be emitted in the output by escaping it as `{{{{`.
+///
+/// # Arguments
+///
+/// * `filepath` - Path of the file to begin parsing.
+/// * `vars` - Named values available for `{{$name}}` substitution.
+///
+pub fn parse_with_vars(filepath: &Path, vars: &HashMap<String, String>) -> Result<Vec<u8>, ParsingError> {
 
     // set up hashmap with owned (canonicalized) paths of parsed files
     let mut parsed_files: HashMap<PathBuf, Vec<u8>> = HashMap::new();
 
+    // stack of canonicalized paths currently being parsed, used to detect cycles
+    let mut ancestors: Vec<PathBuf> = Vec::new();
+
     // call recursive function
-    parse_recursive(&mut parsed_files, filepath)
+    parse_recursive(&mut parsed_files, &mut ancestors, vars, filepath)
 }
 
 // smart recursive function used for parsing files
-fn parse_recursive(parsed_files: &mut HashMap<PathBuf, Vec<u8>>, filepath: &Path) -> Result<Vec<u8>, ParsingError> {
+//
+// `ancestors` holds the canonicalized paths currently on the recursion stack (distinct from
+// `parsed_files`, which memoizes the results of files that have already finished parsing): if a
+// file is already on `ancestors`, parsing it again would recurse forever, so that is reported as
+// a RecursivePath error instead.
+fn parse_recursive(parsed_files: &mut HashMap<PathBuf, Vec<u8>>, ancestors: &mut Vec<PathBuf>, vars: &HashMap<String, String>, filepath: &Path) -> Result<Vec<u8>, ParsingError> {
 
     // buffer storing parsed file contents
     let mut result: Vec<u8> = Vec::new();
@@ -75,49 +106,113 @@ fn parse_recursive(parsed_files: &mut HashMap<PathBuf, Vec<u8>>, filepath: &Path
     // check if we have already parsed this file
     if parsed_files.contains_key(absolute_path.as_path()) {
         result.append(&mut parsed_files[&absolute_path].clone());
-        Ok(result)
-    } else {
-
-        // open and recursively parse the file
-        let file = File::open(filepath)?;
-        let mut reader = BufReader::new(file);
-        while let Some(path) = find_template(&mut reader, &mut result)? {
-            result.append(&mut parse_recursive(parsed_files, filepath.join(path).as_path())?);
+        return Ok(result);
+    }
+
+    // check if this file is already being parsed further up the call stack
+    if ancestors.contains(&absolute_path) {
+        return Err(ParsingError::RecursivePath(absolute_path));
+    }
+
+    ancestors.push(absolute_path.clone());
+
+    // open and recursively parse the file
+    let file = File::open(filepath)?;
+    let mut reader = BufReader::new(file);
+    while let Some(template) = find_template(&mut reader, &mut result)? {
+        match template {
+            Template::Path(path) => {
+                let include_path = normalize_path(&filepath.join(path));
+                result.append(&mut parse_recursive(parsed_files, ancestors, vars, include_path.as_path())?);
+            }
+            Template::Variable(name) => match vars.get(&name) {
+                Some(value) => result.extend_from_slice(value.as_bytes()),
+                None => return Err(ParsingError::UnknownVariable(name)),
+            },
         }
+    }
+
+    ancestors.pop();
 
-        // store the parsed file in the dictionary and return it
-        parsed_files.insert(absolute_path.clone(), result.clone());
-        Ok(result)
+    // store the parsed file in the dictionary and return it
+    parsed_files.insert(absolute_path, result.clone());
+    Ok(result)
+}
+
+// lexically collapses ".." components against the preceding normal component, without touching
+// the filesystem; e.g. "dir/file.txt/../other.txt" normalizes to "dir/other.txt". Templates are
+// written relative to the including file itself (e.g. "../sibling.txt" from within a file means
+// "the directory that file is in"), so joining the raw path onto `filepath` produces a path that
+// walks through the file as if it were a directory -- normalizing it away here avoids ever
+// touching the filesystem with that intermediate, invalid path.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut components: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(components.last(), Some(Component::Normal(_))) => {
+                components.pop();
+            }
+            other => components.push(other),
+        }
     }
+    components.into_iter().collect()
+}
+
+// a {{ }} template, parsed into either a relative file path to include or a $-prefixed variable name
+enum Template {
+    Path(PathBuf),
+    Variable(String),
 }
 
-// finds the first template (if any) in the bufreader stream and returns its path
+// finds the first template (if any) in the bufreader stream and returns it
 // reads the file up to that template into the buffer
 fn find_template(
     reader: &mut BufReader<File>,
     result: &mut Vec<u8>,
-) -> Result<Option<PathBuf>, ParsingError> {
+) -> Result<Option<Template>, ParsingError> {
 
-    // find template start {{
-    if find_repeated_byte(reader, b'{', result)? {
-        let open = result.len();
-
-        // find template end }}
-        if find_repeated_byte(reader, b'}', result)? {
+    // find an unescaped template start {{ (a run of four braces, {{{{, is a literal {{ and is skipped)
+    loop {
+        if !find_repeated_byte(reader, b'{', result)? {
+            return Ok(None);
+        }
+        if !skip_literal_escape(reader, b'{')? {
+            break;
+        }
+    }
 
-            // get path from template
-            let path_slice = result.split_off(open - 2);
-            let path = from_utf8(&path_slice[2..path_slice.len() - 2])?;
+    let open = result.len();
 
-            // convert to path
-            return match PathBuf::from_str(path) {
-                Ok(path_buf) => Ok(Some(path_buf)),
-                Err(_) => Err(ParsingError::MalformedTemplatePath(path.to_string())),
-            };
-        }
+    // find template end }}
+    if !find_repeated_byte(reader, b'}', result)? {
+        return Ok(None);
     }
 
-    Ok(None)
+    // get contents from between the braces
+    let contents_slice = result.split_off(open - 2);
+    let contents = from_utf8(&contents_slice[2..contents_slice.len() - 2])?;
+
+    // a leading $ names a variable; otherwise the contents are a relative file path
+    Ok(Some(match contents.strip_prefix('$') {
+        Some(name) => Template::Variable(name.to_string()),
+        None => match PathBuf::from_str(contents) {
+            Ok(path_buf) => Template::Path(path_buf),
+            Err(_) => return Err(ParsingError::MalformedTemplatePath(contents.to_string())),
+        },
+    }))
+}
+
+// checks whether the next two bytes in the stream are also `byte`; if so, consumes and discards
+// them, since a run of four braces ({{{{) is an escaped literal {{ rather than the start of a
+// second template
+fn skip_literal_escape(reader: &mut BufReader<File>, byte: u8) -> Result<bool, ParsingError> {
+    let buffer = reader.fill_buf()?;
+    if buffer.len() >= 2 && buffer[0] == byte && buffer[1] == byte {
+        reader.consume(2);
+        return Ok(true);
+    }
+    Ok(false)
 }
 
 // checks for the first instance of the given byte (if any) in the bufreader stream
@@ -200,4 +295,94 @@ mod tests {
         remove_dir_all(test_dir)?;
         Ok(())
     }
+
+    #[test]
+    fn test_parse_detects_direct_self_inclusion() -> Result<(), Box<dyn Error>> {
+        let test_dir = Path::new("./test_templates_3");
+        create_dir(test_dir)?;
+
+        let test_template = test_dir.join(Path::new("test_template.txt"));
+        write(test_template.clone(), "before {{../test_template.txt}} after")?;
+
+        match parse(test_template.as_path()) {
+            Err(ParsingError::RecursivePath(_)) => {}
+            other => panic!("expected RecursivePath error, got {:?}", other),
+        }
+
+        remove_dir_all(test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_detects_mutual_inclusion_cycle() -> Result<(), Box<dyn Error>> {
+        let test_dir = Path::new("./test_templates_4");
+        create_dir(test_dir)?;
+
+        let template_a = test_dir.join(Path::new("template_a.txt"));
+        let template_b = test_dir.join(Path::new("template_b.txt"));
+        write(template_a.clone(), "a includes {{../template_b.txt}}")?;
+        write(template_b.clone(), "b includes {{../template_a.txt}}")?;
+
+        match parse(template_a.as_path()) {
+            Err(ParsingError::RecursivePath(_)) => {}
+            other => panic!("expected RecursivePath error, got {:?}", other),
+        }
+
+        remove_dir_all(test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_with_vars_substitutes_variable() -> Result<(), Box<dyn Error>> {
+        let test_dir = Path::new("./test_templates_5");
+        create_dir(test_dir)?;
+
+        let test_template = test_dir.join(Path::new("test_template.txt"));
+        write(test_template.clone(), "hello {{$name}}, welcome to {{$site_name}}")?;
+
+        let mut vars = HashMap::new();
+        vars.insert("name".to_string(), "world".to_string());
+        vars.insert("site_name".to_string(), "the site".to_string());
+
+        let res = parse_with_vars(test_template.as_path(), &vars)?;
+        assert_eq!("hello world, welcome to the site", std::str::from_utf8(&*res).unwrap());
+
+        remove_dir_all(test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_with_vars_errors_on_unknown_variable() -> Result<(), Box<dyn Error>> {
+        let test_dir = Path::new("./test_templates_6");
+        create_dir(test_dir)?;
+
+        let test_template = test_dir.join(Path::new("test_template.txt"));
+        write(test_template.clone(), "hello {{$name}}")?;
+
+        match parse_with_vars(test_template.as_path(), &HashMap::new()) {
+            Err(ParsingError::UnknownVariable(name)) => assert_eq!("name", name),
+            other => panic!("expected UnknownVariable error, got {:?}", other),
+        }
+
+        remove_dir_all(test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_escapes_literal_double_brace() -> Result<(), Box<dyn Error>> {
+        let test_dir = Path::new("./test_templates_7");
+        create_dir(test_dir)?;
+
+        let test_template = test_dir.join(Path::new("test_template.txt"));
+        write(test_template.clone(), "literal {{{{ braces, plus {{$name}}")?;
+
+        let mut vars = HashMap::new();
+        vars.insert("name".to_string(), "a variable".to_string());
+
+        let res = parse_with_vars(test_template.as_path(), &vars)?;
+        assert_eq!("literal {{ braces, plus a variable", std::str::from_utf8(&*res).unwrap());
+
+        remove_dir_all(test_dir)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,291 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::graph::Graph;
+
+const BARYCENTER_SWEEPS: usize = 4;
+
+/// Computes a layered (Sugiyama-style) drawing of a directed graph and returns, for every vertex
+/// label (including dummy vertices inserted along edges spanning more than one layer), its
+/// `(layer, x, y)` position. The result is consumable by the DOT exporter or an SVG writer.
+pub fn layout<T: Eq + PartialEq, L: Ord + Clone>(graph: &Graph<T, L>) -> HashMap<usize, (usize, usize, usize)> {
+    let labels: Vec<usize> = graph.vertex_labels().collect();
+
+    let mut edges: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for &label in &labels {
+        for (_, target) in graph.neighbors(&label) {
+            edges.insert((label, target));
+        }
+    }
+
+    // if the graph has a cycle, temporarily reverse a feedback arc set so layering can proceed
+    let working_edges: Vec<(usize, usize)> = if graph.has_cycle() {
+        let feedback = feedback_arc_set(&labels, &edges);
+        edges.iter()
+            .map(|&(source, target)| {
+                if feedback.contains(&(source, target)) { (target, source) } else { (source, target) }
+            })
+            .collect()
+    } else {
+        edges.into_iter().collect()
+    };
+
+    // assign each vertex to a layer by longest-path layering, computed in topological order
+    let mut layer = longest_path_layers(&labels, &working_edges);
+
+    // insert dummy vertices along edges spanning more than one layer, so every edge connects adjacent layers
+    let mut next_dummy_label = labels.iter().copied().max().map(|max| max + 1).unwrap_or(0);
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut parents: HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for &(source, target) in &working_edges {
+        let span = layer[&target] - layer[&source];
+        let mut previous = source;
+        for step in 1..span {
+            let dummy = next_dummy_label;
+            next_dummy_label += 1;
+            layer.insert(dummy, layer[&source] + step);
+            children.entry(previous).or_default().push(dummy);
+            parents.entry(dummy).or_default().push(previous);
+            previous = dummy;
+        }
+        children.entry(previous).or_default().push(target);
+        parents.entry(target).or_default().push(previous);
+    }
+
+    // group vertices (real and dummy) by layer
+    let max_layer = layer.values().copied().max().unwrap_or(0);
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); max_layer + 1];
+    for (&label, &vertex_layer) in &layer {
+        layers[vertex_layer].push(label);
+    }
+    for layer_vertices in &mut layers {
+        layer_vertices.sort();
+    }
+
+    let mut position: HashMap<usize, usize> = HashMap::new();
+    for layer_vertices in &layers {
+        for (index, &label) in layer_vertices.iter().enumerate() {
+            position.insert(label, index);
+        }
+    }
+
+    // reduce crossings with repeated barycenter sweeps, alternating downward and upward passes:
+    // a vertex's position becomes the average position of its neighbors in the adjacent layer
+    for iteration in 0..BARYCENTER_SWEEPS {
+        if iteration % 2 == 0 {
+            for layer_vertices in layers.iter_mut().skip(1) {
+                reorder_by_barycenter(layer_vertices, &parents, &position);
+                for (index, &label) in layer_vertices.iter().enumerate() {
+                    position.insert(label, index);
+                }
+            }
+        } else {
+            for layer_vertices in layers.iter_mut().rev().skip(1) {
+                reorder_by_barycenter(layer_vertices, &children, &position);
+                for (index, &label) in layer_vertices.iter().enumerate() {
+                    position.insert(label, index);
+                }
+            }
+        }
+    }
+
+    // assign x-coordinates from the within-layer ordering and y-coordinates from the layer index
+    layer.iter()
+        .map(|(&label, &vertex_layer)| (label, (vertex_layer, position[&label], vertex_layer)))
+        .collect()
+}
+
+const SVG_NODE_SPACING: f64 = 80.0;
+const SVG_NODE_RADIUS: f64 = 20.0;
+
+/// Renders a lightweight SVG diagram of the graph's topology, positioned by `layout`: a labelled
+/// circle per vertex (dummy vertices inserted for long edges are skipped) and a line per edge.
+pub fn to_svg<T: Eq + PartialEq, L: Ord + Clone>(graph: &Graph<T, L>) -> String {
+    let positions = layout(graph);
+
+    let point = |label: usize| -> (f64, f64) {
+        let (_, x, y) = positions[&label];
+        (x as f64 * SVG_NODE_SPACING + SVG_NODE_RADIUS, y as f64 * SVG_NODE_SPACING + SVG_NODE_RADIUS)
+    };
+
+    let max_x = positions.values().map(|&(_, x, _)| x).max().unwrap_or(0);
+    let max_y = positions.values().map(|&(_, _, y)| y).max().unwrap_or(0);
+    let width = (max_x as f64 + 1.0) * SVG_NODE_SPACING;
+    let height = (max_y as f64 + 1.0) * SVG_NODE_SPACING;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+        width, height
+    );
+
+    for label in graph.vertex_labels() {
+        for (_, target) in graph.neighbors(&label) {
+            let (x1, y1) = point(label);
+            let (x2, y2) = point(target);
+            svg.push_str(&format!("  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>\n", x1, y1, x2, y2));
+        }
+    }
+
+    for label in graph.vertex_labels() {
+        let (x, y) = point(label);
+        svg.push_str(&format!(
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"white\" stroke=\"black\"/>\n  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            x, y, SVG_NODE_RADIUS, x, y + 4.0, label
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// reorders a layer's vertices by the average position of their neighbors in the adjacent layer
+fn reorder_by_barycenter(layer_vertices: &mut Vec<usize>, adjacency: &HashMap<usize, Vec<usize>>, position: &HashMap<usize, usize>) {
+    let mut barycenters: Vec<(usize, f64)> = layer_vertices.iter().map(|&label| {
+        let barycenter = match adjacency.get(&label) {
+            Some(neighbors) if !neighbors.is_empty() => {
+                neighbors.iter().map(|neighbor| position[neighbor] as f64).sum::<f64>() / neighbors.len() as f64
+            }
+            _ => position[&label] as f64,
+        };
+        (label, barycenter)
+    }).collect();
+
+    barycenters.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    *layer_vertices = barycenters.into_iter().map(|(label, _)| label).collect();
+}
+
+// assigns each vertex a layer one greater than the max layer of its predecessors, in topological order
+fn longest_path_layers(labels: &[usize], edges: &[(usize, usize)]) -> HashMap<usize, usize> {
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut in_degree: HashMap<usize, usize> = labels.iter().map(|&label| (label, 0)).collect();
+    for &(source, target) in edges {
+        children.entry(source).or_default().push(target);
+        *in_degree.entry(target).or_insert(0) += 1;
+    }
+
+    let mut remaining_in_degree = in_degree.clone();
+    let mut queue: VecDeque<usize> = labels.iter().copied().filter(|label| in_degree[label] == 0).collect();
+    let mut layer: HashMap<usize, usize> = labels.iter().map(|&label| (label, 0)).collect();
+
+    while let Some(source) = queue.pop_front() {
+        if let Some(targets) = children.get(&source) {
+            for &target in targets {
+                layer.insert(target, layer[&target].max(layer[&source] + 1));
+                let degree = remaining_in_degree.get_mut(&target).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(target);
+                }
+            }
+        }
+    }
+
+    layer
+}
+
+// finds a feedback arc set via DFS: edges that point back at an ancestor on the current recursion stack
+fn feedback_arc_set(labels: &[usize], edges: &BTreeSet<(usize, usize)>) -> HashSet<(usize, usize)> {
+    let mut adjacency: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(source, target) in edges {
+        adjacency.entry(source).or_default().push(target);
+    }
+
+    let mut visited = HashSet::new();
+    let mut on_stack = HashSet::new();
+    let mut back_edges = HashSet::new();
+
+    for &label in labels {
+        if !visited.contains(&label) {
+            feedback_arc_set_dfs(label, &adjacency, &mut visited, &mut on_stack, &mut back_edges);
+        }
+    }
+
+    back_edges
+}
+
+fn feedback_arc_set_dfs(
+    source: usize,
+    adjacency: &HashMap<usize, Vec<usize>>,
+    visited: &mut HashSet<usize>,
+    on_stack: &mut HashSet<usize>,
+    back_edges: &mut HashSet<(usize, usize)>,
+) {
+    visited.insert(source);
+    on_stack.insert(source);
+
+    if let Some(targets) = adjacency.get(&source) {
+        for &target in targets {
+            if on_stack.contains(&target) {
+                back_edges.insert((source, target));
+            } else if !visited.contains(&target) {
+                feedback_arc_set_dfs(target, adjacency, visited, on_stack, back_edges);
+            }
+        }
+    }
+
+    on_stack.remove(&source);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_simple_chain() {
+        let mut graph: Graph<&str, ()> = Graph::new();
+        let a = graph.add_vertex("a");
+        let b = graph.add_vertex("b");
+        let c = graph.add_vertex("c");
+        graph.add_edge(&a, b, ()).unwrap();
+        graph.add_edge(&b, c, ()).unwrap();
+
+        let positions = layout(&graph);
+        assert_eq!(0, positions[&a].0);
+        assert_eq!(1, positions[&b].0);
+        assert_eq!(2, positions[&c].0);
+    }
+
+    #[test]
+    fn test_layout_inserts_dummy_vertices_for_long_edges() {
+        let mut graph: Graph<&str, ()> = Graph::new();
+        let a = graph.add_vertex("a");
+        let b = graph.add_vertex("b");
+        let c = graph.add_vertex("c");
+        graph.add_edge(&a, b, ()).unwrap();
+        graph.add_edge(&b, c, ()).unwrap();
+        graph.add_edge(&a, c, ()).unwrap();
+
+        // a -> c spans two layers, so one dummy vertex should be inserted between them
+        let positions = layout(&graph);
+        assert_eq!(4, positions.len());
+        assert!(positions.values().filter(|&&(layer, _, _)| layer == 1).count() == 2);
+    }
+
+    #[test]
+    fn test_layout_handles_cycles() {
+        let mut graph: Graph<&str, ()> = Graph::new();
+        let a = graph.add_vertex("a");
+        let b = graph.add_vertex("b");
+        graph.add_edge(&a, b, ()).unwrap();
+        graph.add_edge(&b, a, ()).unwrap();
+
+        // should not infinite loop, and both vertices should still get a layer assigned
+        let positions = layout(&graph);
+        assert_eq!(2, positions.len());
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let mut graph: Graph<&str, ()> = Graph::new();
+        let a = graph.add_vertex("a");
+        let b = graph.add_vertex("b");
+        graph.add_edge(&a, b, ()).unwrap();
+
+        let svg = to_svg(&graph);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("<line "));
+        assert!(svg.contains(&format!(">{}<", a)));
+        assert!(svg.contains(&format!(">{}<", b)));
+    }
+}